@@ -0,0 +1,217 @@
+use serde::Deserialize;
+use std::path::Path;
+
+/// Per-repo defaults loaded from a `.gh-autopr.toml` at the repo root.
+/// Every field is optional so teams only need to set what they want to
+/// override.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RepoConfig {
+    /// Overrides the auto-detected `origin/HEAD` main branch name.
+    pub main_branch: Option<String>,
+    /// Prefixes generated commit titles with a Conventional Commits type
+    /// (`feat:`, `fix:`, ...) when true.
+    #[serde(default)]
+    pub conventional_commits: bool,
+    /// PR body template. Supports the `{diff_summary}` and `{commit_title}`
+    /// placeholders; falls back to the generated PR body when unset.
+    pub pr_body_template: Option<String>,
+    /// Labels passed to `gh pr create --label`.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Reviewers passed to `gh pr create --reviewer`.
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
+impl RepoConfig {
+    const FILE_NAME: &'static str = ".gh-autopr.toml";
+
+    /// Loads `.gh-autopr.toml` from `repo_root`, falling back to defaults if
+    /// the file doesn't exist.
+    pub fn load(repo_root: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = repo_root.join(Self::FILE_NAME);
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&config_path)?;
+        Self::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: RepoConfig = toml::from_str(contents)?;
+        Ok(config)
+    }
+
+    /// Applies the `conventional_commits` setting to a generated commit
+    /// title, leaving it untouched if the title already carries a type
+    /// prefix or the setting is off. The type is inferred from the title's
+    /// leading verb rather than always tagging `chore`, so the prefix stays
+    /// meaningful to changelog/semver tooling.
+    pub fn apply_commit_convention(&self, commit_title: &str) -> String {
+        // A type prefix is a single word (optionally with a `(scope)` or `!`)
+        // before the first colon, e.g. `feat:` or `fix(ui)!:`.
+        let has_type_prefix = commit_title.split_once(':').map_or(false, |(prefix, _)| {
+            !prefix.is_empty() && !prefix.contains(char::is_whitespace)
+        });
+        if !self.conventional_commits || has_type_prefix {
+            return commit_title.to_string();
+        }
+        format!(
+            "{}: {}",
+            infer_conventional_commit_type(commit_title),
+            commit_title
+        )
+    }
+
+    /// Renders the PR body template, if configured, substituting
+    /// `{commit_title}` and `{diff_summary}`.
+    pub fn render_pr_body(&self, commit_title: &str, generated_body: &str) -> String {
+        match &self.pr_body_template {
+            Some(template) => template
+                .replace("{commit_title}", commit_title)
+                .replace("{diff_summary}", generated_body),
+            None => generated_body.to_string(),
+        }
+    }
+}
+
+/// Guesses a Conventional Commits type from a title's leading verb, falling
+/// back to `chore` for anything that doesn't match a more specific type.
+fn infer_conventional_commit_type(commit_title: &str) -> &'static str {
+    let title = commit_title.trim_start().to_lowercase();
+    let starts_with_any = |words: &[&str]| words.iter().any(|word| title.starts_with(word));
+
+    if starts_with_any(&["fix", "resolve", "correct"]) {
+        "fix"
+    } else if starts_with_any(&["add", "introduce", "support", "implement"]) {
+        "feat"
+    } else if starts_with_any(&["remove", "delete", "refactor", "rename", "extract"]) {
+        "refactor"
+    } else if starts_with_any(&["doc", "docs"]) {
+        "docs"
+    } else if starts_with_any(&["test"]) {
+        "test"
+    } else {
+        "chore"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_commit_convention_leaves_title_untouched_when_disabled() {
+        let config = RepoConfig::default();
+        assert_eq!(
+            config.apply_commit_convention("Add widget support"),
+            "Add widget support"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_skips_titles_that_already_have_a_type() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("feat: add widget support"),
+            "feat: add widget support"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_infers_fix() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Fix the flaky upload test"),
+            "fix: Fix the flaky upload test"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_infers_feat() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Add widget support"),
+            "feat: Add widget support"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_infers_refactor() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Remove dead code path"),
+            "refactor: Remove dead code path"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_infers_docs() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Document the setup process"),
+            "docs: Document the setup process"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_infers_test() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Test the retry backoff logic"),
+            "test: Test the retry backoff logic"
+        );
+    }
+
+    #[test]
+    fn apply_commit_convention_falls_back_to_chore() {
+        let config = RepoConfig {
+            conventional_commits: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.apply_commit_convention("Bump dependency versions"),
+            "chore: Bump dependency versions"
+        );
+    }
+
+    #[test]
+    fn render_pr_body_substitutes_placeholders() {
+        let config = RepoConfig {
+            pr_body_template: Some("## {commit_title}\n\n{diff_summary}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.render_pr_body("Add widget support", "Adds the Widget type."),
+            "## Add widget support\n\nAdds the Widget type."
+        );
+    }
+
+    #[test]
+    fn render_pr_body_falls_back_to_generated_body_without_template() {
+        let config = RepoConfig::default();
+        assert_eq!(
+            config.render_pr_body("Add widget support", "Adds the Widget type."),
+            "Adds the Widget type."
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::git::Git;
+use crate::tui::App;
+use once_cell::sync::OnceCell;
+
+/// Splits a NUL-separated `git ls-files -z` / `git diff --name-only -z`
+/// stream into individual paths.
+fn parse_nul_separated(bytes: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(bytes)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Determines which package roots a change touches. Caches the repo-wide
+/// tracked-file list with `OnceCell` so repeated calls within one run don't
+/// re-shell-out to git.
+pub struct Determinator {
+    git: Git,
+    tracked_files: OnceCell<Vec<String>>,
+}
+
+impl Determinator {
+    pub fn new(git: Git) -> Self {
+        Self {
+            git,
+            tracked_files: OnceCell::new(),
+        }
+    }
+
+    /// All paths tracked by git, fetched once per `Determinator` and cached
+    /// for subsequent calls.
+    pub fn tracked_files(&self, app: &mut App) -> Result<&Vec<String>, Box<dyn std::error::Error>> {
+        if let Some(files) = self.tracked_files.get() {
+            return Ok(files);
+        }
+
+        let output = self.git.command(&["ls-files", "-z"]).output()?;
+        if !output.status.success() {
+            app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+            return Err("Failed to list tracked files".into());
+        }
+
+        let files = parse_nul_separated(&output.stdout);
+        Ok(self.tracked_files.get_or_init(|| files))
+    }
+
+    /// Paths that differ between `main_branch` and `current_branch`.
+    pub fn changed_paths(
+        &self,
+        app: &mut App,
+        main_branch: &str,
+        current_branch: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let output = self
+            .git
+            .command(&[
+                "diff",
+                "--name-only",
+                "-z",
+                &format!("{}...{}", main_branch, current_branch),
+            ])
+            .output()?;
+        if !output.status.success() {
+            app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+            return Err("Failed to list changed paths".into());
+        }
+
+        Ok(parse_nul_separated(&output.stdout))
+    }
+
+    /// Groups changed paths by the directory of the deepest tracked
+    /// `Cargo.toml` that contains them, or by their top-level directory when
+    /// no tracked manifest does, so a monorepo diff can be scoped to just the
+    /// affected components. Ensures the tracked-file cache is populated first, so
+    /// manifest-aware grouping can't silently degrade to top-level-directory
+    /// grouping just because `tracked_files` wasn't called yet.
+    pub fn affected_package_roots(
+        &self,
+        app: &mut App,
+        changed_paths: &[String],
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let tracked_manifests: Vec<&str> = self
+            .tracked_files(app)?
+            .iter()
+            .filter(|path| path.ends_with("Cargo.toml"))
+            .map(String::as_str)
+            .collect();
+
+        let mut roots = std::collections::BTreeSet::new();
+        for path in changed_paths {
+            let root = tracked_manifests
+                .iter()
+                .filter_map(|manifest| manifest.strip_suffix("Cargo.toml"))
+                .filter(|manifest_dir| path.starts_with(manifest_dir))
+                .max_by_key(|manifest_dir| manifest_dir.len())
+                .map(|manifest_dir| manifest_dir.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| {
+                    path.split('/').next().unwrap_or(path.as_str()).to_string()
+                });
+            roots.insert(root);
+        }
+
+        Ok(roots.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::App;
+    use std::process::Command;
+
+    #[test]
+    fn parse_nul_separated_splits_and_drops_empties() {
+        let bytes = b"a.rs\0b/c.rs\0";
+        assert_eq!(
+            parse_nul_separated(bytes),
+            vec!["a.rs".to_string(), "b/c.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn affected_package_roots_groups_by_nearest_manifest() {
+        let determinator = Determinator::new(Git::new());
+        determinator
+            .tracked_files
+            .set(vec![
+                "crates/foo/Cargo.toml".to_string(),
+                "crates/foo/src/lib.rs".to_string(),
+                "crates/bar/Cargo.toml".to_string(),
+                "README.md".to_string(),
+            ])
+            .unwrap();
+
+        let mut app = App::new();
+        let roots = determinator
+            .affected_package_roots(
+                &mut app,
+                &[
+                    "crates/foo/src/lib.rs".to_string(),
+                    "crates/bar/src/main.rs".to_string(),
+                    "docs/overview.md".to_string(),
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            roots,
+            vec![
+                "crates/bar".to_string(),
+                "crates/foo".to_string(),
+                "docs".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn tracked_files_is_cached_after_the_first_call() {
+        let repo = tempfile::tempdir().unwrap();
+        Command::new("git")
+            .args(["init"])
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+        std::fs::write(repo.path().join("a.txt"), "a").unwrap();
+        Command::new("git")
+            .args(["add", "a.txt"])
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(repo.path()).unwrap();
+
+        let determinator = Determinator::new(Git::new());
+        let mut app = App::new();
+        let first = determinator.tracked_files(&mut app).unwrap().clone();
+
+        // A file added after the first call shouldn't show up in the second
+        // call's result if the list is truly cached rather than re-fetched.
+        std::fs::write(repo.path().join("b.txt"), "b").unwrap();
+        Command::new("git")
+            .args(["add", "b.txt"])
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+
+        let second = determinator.tracked_files(&mut app).unwrap().clone();
+
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(first, vec!["a.txt".to_string()]);
+        assert_eq!(first, second);
+    }
+}
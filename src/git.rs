@@ -0,0 +1,29 @@
+use std::process::Command;
+
+/// Wraps `git` invocations with a fixed set of global arguments applied to
+/// every subcommand, instead of calling `Command::new("git")` directly at
+/// each call site.
+///
+/// `for_repo`/`root()`-style scoping (running against a repo other than the
+/// process's current directory, via `-C`) isn't implemented yet — nothing
+/// in gh-autopr parses a `--repo` flag to feed it, so it would be unused
+/// plumbing. Add it back alongside that flag when it lands.
+#[derive(Debug, Clone, Default)]
+pub struct Git {
+    global_args: Vec<String>,
+}
+
+impl Git {
+    /// Run `git` against whatever the process's current directory is.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a `git <args>` command with the global args prepended.
+    pub fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new("git");
+        command.args(&self.global_args);
+        command.args(args);
+        command
+    }
+}
@@ -1,11 +1,13 @@
+use crate::config::RepoConfig;
+use crate::git::Git;
 use crate::tui::{render_message, App};
 use ratatui::style::Color;
 use ratatui::{backend::Backend, Terminal};
 use std::process::Command;
 
-pub fn git_ensure_in_repo(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--is-inside-work-tree"])
+pub fn git_ensure_in_repo(git: &Git, app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
+    let output = git
+        .command(&["rev-parse", "--is-inside-work-tree"])
         .output()?;
 
     if !output.status.success() {
@@ -37,26 +39,12 @@ pub fn git_ensure_not_detached_head<B: Backend>(
     Ok(())
 }
 
-pub fn git_cd_to_repo_root(app: &mut App) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
-        .output()?;
-    if output.status.success() {
-        let repo_root = String::from_utf8(output.stdout)?.trim().to_string();
-        std::env::set_current_dir(&repo_root)?;
-        app.add_log(
-            "INFO",
-            format!("Changed directory to repo root: {}", repo_root),
-        );
-    } else {
-        app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
-    }
-    Ok(())
-}
-
-pub fn git_diff_uncommitted(app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args(["diff", "--cached", "--", ".", ":!*.lock"])
+pub fn git_diff_uncommitted(
+    git: &Git,
+    app: &mut App,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let output = git
+        .command(&["diff", "--cached", "--", ".", ":!*.lock"])
         .output()?;
 
     if !output.status.success() {
@@ -67,9 +55,7 @@ pub fn git_diff_uncommitted(app: &mut App) -> Result<String, Box<dyn std::error:
     let diff_context = String::from_utf8(output.stdout)?.trim().to_string();
 
     if diff_context.is_empty() {
-        let output = Command::new("git")
-            .args(["diff", "--", ".", ":!*.lock"])
-            .output()?;
+        let output = git.command(&["diff", "--", ".", ":!*.lock"]).output()?;
 
         if !output.status.success() {
             app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
@@ -83,18 +69,35 @@ pub fn git_diff_uncommitted(app: &mut App) -> Result<String, Box<dyn std::error:
 }
 
 pub fn git_diff_between_branches(
+    git: &Git,
     app: &mut App,
     main_branch: &String,
     current_branch: &String,
 ) -> Result<String, Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args([
-            "diff",
-            &format!("{}...{}", main_branch, current_branch),
-            "--",
-            ":!*.lock",
-        ])
-        .output()?;
+    git_diff_between_branches_scoped(git, app, main_branch, current_branch, &[])
+}
+
+/// Like [`git_diff_between_branches`], but restricted to `package_roots` when
+/// non-empty. Pair with [`crate::determinator::Determinator::affected_package_roots`]
+/// to keep the PR/commit summary for a monorepo change limited to the
+/// components it actually touches.
+pub fn git_diff_between_branches_scoped(
+    git: &Git,
+    app: &mut App,
+    main_branch: &String,
+    current_branch: &String,
+    package_roots: &[String],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut args = vec![
+        "diff".to_string(),
+        format!("{}...{}", main_branch, current_branch),
+        "--".to_string(),
+        ":!*.lock".to_string(),
+    ];
+    args.extend(package_roots.iter().cloned());
+    let arg_refs: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let output = git.command(&arg_refs).output()?;
 
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
@@ -104,15 +107,27 @@ pub fn git_diff_between_branches(
     Ok(String::from_utf8(output.stdout)?.trim().to_string())
 }
 
-pub fn git_main_branch(app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
-    let mut main_branch_output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "origin/HEAD"])
+pub fn git_main_branch(
+    git: &Git,
+    app: &mut App,
+    config: &RepoConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some(main_branch) = &config.main_branch {
+        app.add_log(
+            "INFO",
+            format!("Using configured main branch: {}", main_branch),
+        );
+        return Ok(main_branch.clone());
+    }
+
+    let mut main_branch_output = git
+        .command(&["rev-parse", "--abbrev-ref", "origin/HEAD"])
         .output()?;
 
     if !main_branch_output.status.success() {
         app.add_log("INFO", "Setting origin HEAD automatically...");
-        let output = Command::new("git")
-            .args(["remote", "set-head", "origin", "--auto"])
+        let output = git
+            .command(&["remote", "set-head", "origin", "--auto"])
             .output()?;
 
         if !output.status.success() {
@@ -120,8 +135,8 @@ pub fn git_main_branch(app: &mut App) -> Result<String, Box<dyn std::error::Erro
             return Err("Failed to set origin HEAD".into());
         }
 
-        main_branch_output = Command::new("git")
-            .args(["rev-parse", "--abbrev-ref", "origin/HEAD"])
+        main_branch_output = git
+            .command(&["rev-parse", "--abbrev-ref", "origin/HEAD"])
             .output()?;
 
         if !main_branch_output.status.success() {
@@ -138,10 +153,8 @@ pub fn git_main_branch(app: &mut App) -> Result<String, Box<dyn std::error::Erro
     Ok(branch)
 }
 
-pub fn git_current_branch(app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()?;
+pub fn git_current_branch(git: &Git, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+    let output = git.command(&["rev-parse", "--abbrev-ref", "HEAD"]).output()?;
 
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
@@ -153,21 +166,141 @@ pub fn git_current_branch(app: &mut App) -> Result<String, Box<dyn std::error::E
     Ok(branch)
 }
 
+/// A local branch and the Unix timestamp of its tip commit, as reported by
+/// `git for-each-ref`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchInfo {
+    pub name: String,
+    pub committed_at: i64,
+}
+
+/// Lists local branches with their tip commit's timestamp, most recently
+/// committed first, for a timestamp-sorted branch picker. Nothing in the
+/// commit flow calls this function or `ensure_feature_branch` below.
+pub fn git_branches(git: &Git, app: &mut App) -> Result<Vec<BranchInfo>, Box<dyn std::error::Error>> {
+    let output = git
+        .command(&[
+            "for-each-ref",
+            "--format=%(refname:short) %(committerdate:unix)",
+            "refs/heads/",
+        ])
+        .output()?;
+
+    if !output.status.success() {
+        app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err("Failed to list branches".into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut branches: Vec<BranchInfo> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, timestamp) = line.rsplit_once(' ')?;
+            Some(BranchInfo {
+                name: name.to_string(),
+                committed_at: timestamp.trim().parse().ok()?,
+            })
+        })
+        .collect();
+
+    branches.sort_by_key(|branch| std::cmp::Reverse(branch.committed_at));
+    Ok(branches)
+}
+
+/// Creates `branch_name` off the current `HEAD` and switches to it.
+pub fn create_branch(
+    git: &Git,
+    app: &mut App,
+    branch_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = git.command(&["checkout", "-b", branch_name]).output()?;
+    if !output.status.success() {
+        app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err("Failed to create branch".into());
+    }
+    app.add_log("INFO", format!("Created and switched to branch {}", branch_name));
+    Ok(())
+}
+
+/// Switches to an existing local branch.
+pub fn switch_branch(
+    git: &Git,
+    app: &mut App,
+    branch_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = git.command(&["checkout", branch_name]).output()?;
+    if !output.status.success() {
+        app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err("Failed to switch branch".into());
+    }
+    app.add_log("INFO", format!("Switched to branch {}", branch_name));
+    Ok(())
+}
+
+/// Turns a generated commit title into a branch-name-safe slug, e.g. for
+/// seeding a new feature branch name from the commit title the PR generator
+/// produced.
+pub fn slugify_branch_name(title: &str) -> String {
+    let slug: String = title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+
+    let mut deduped = String::new();
+    let mut last_was_dash = false;
+    for c in slug.chars() {
+        if c == '-' {
+            if !last_was_dash {
+                deduped.push(c);
+            }
+            last_was_dash = true;
+        } else {
+            deduped.push(c);
+            last_was_dash = false;
+        }
+    }
+
+    deduped.trim_matches('-').to_string()
+}
+
+/// If the current branch is `main_branch` or a detached `HEAD`, creates and
+/// switches to a fresh feature branch slugified from `commit_title`, so a
+/// commit made afterwards doesn't land on `main_branch`. Otherwise returns
+/// the current branch unchanged. The TUI does not call it.
+pub fn ensure_feature_branch(
+    git: &Git,
+    app: &mut App,
+    main_branch: &str,
+    current_branch: &str,
+    commit_title: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if current_branch != main_branch && current_branch != "HEAD" {
+        return Ok(current_branch.to_string());
+    }
+
+    let branch_name = slugify_branch_name(commit_title);
+    create_branch(git, app, &branch_name)?;
+    Ok(branch_name)
+}
+
 pub fn git_fetch_main(
+    git: &Git,
     app: &mut App,
     current_branch: &String,
     main_branch: &String,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if current_branch == main_branch {
-        let output = Command::new("git").args(["pull", "origin"]).output()?;
+        let output = git.command(&["pull", "origin"]).output()?;
         if !output.status.success() {
             app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
             return Err("Failed to pull from origin".into());
         }
         app.add_log("INFO", "Pulled latest changes from origin");
     } else {
-        let output = Command::new("git")
-            .args([
+        let output = git
+            .command(&[
                 "fetch",
                 "origin",
                 format!("{}:{}", main_branch, main_branch).as_str(),
@@ -183,26 +316,157 @@ pub fn git_fetch_main(
     Ok(())
 }
 
+/// Status of a single path as reported by `git status --porcelain=v2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileState {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+    Unmerged,
+    Untracked,
+}
+
+/// One entry of `git status --porcelain=v2`, decoded into its path and
+/// staged/unstaged state. Renamed and copied entries report the new path
+/// only, not the `old -> new` pair v1 would otherwise collapse into a single
+/// unparseable field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStatus {
+    pub path: String,
+    pub staged: bool,
+    pub unstaged: bool,
+    pub state: FileState,
+}
+
+fn file_state_from_code(code: char) -> Option<FileState> {
+    match code {
+        'M' => Some(FileState::Modified),
+        'A' => Some(FileState::Added),
+        'D' => Some(FileState::Deleted),
+        'R' => Some(FileState::Renamed),
+        'C' => Some(FileState::Copied),
+        'U' => Some(FileState::Unmerged),
+        _ => None,
+    }
+}
+
+/// `git status --porcelain=v2` uses `.` (not a space) for the "unchanged in
+/// this column" placeholder, e.g. `.M` for an unstaged-only modify or `A.`
+/// for a fully-staged add.
+fn is_unchanged_code(code: char) -> bool {
+    code == '.' || code == ' '
+}
+
+fn file_status_from_xy(xy: &str, path: String) -> FileStatus {
+    let mut codes = xy.chars();
+    let index_code = codes.next().unwrap_or('.');
+    let worktree_code = codes.next().unwrap_or('.');
+    let state = file_state_from_code(index_code)
+        .or_else(|| file_state_from_code(worktree_code))
+        .unwrap_or(FileState::Modified);
+
+    FileStatus {
+        path,
+        staged: !is_unchanged_code(index_code),
+        unstaged: !is_unchanged_code(worktree_code),
+        state,
+    }
+}
+
+/// Parses `git status --porcelain=v2` into a structured changeset so the TUI
+/// can show per-file review before committing, instead of blindly staging
+/// everything.
+pub fn git_status(git: &Git, app: &mut App) -> Result<Vec<FileStatus>, Box<dyn std::error::Error>> {
+    let output = git.command(&["status", "--porcelain=v2"]).output()?;
+    if !output.status.success() {
+        app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+        return Err("Failed to get status".into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut entries = Vec::new();
+    for line in stdout.lines() {
+        let mut fields = line.splitn(2, ' ');
+        let entry_type = fields.next().unwrap_or_default();
+        let rest = fields.next().unwrap_or_default();
+
+        match entry_type {
+            // "1 <XY> <sub> <mH> <mI> <mW> <hH> <hI> <path>"
+            "1" => {
+                let mut parts = rest.splitn(8, ' ');
+                let xy = parts.next().unwrap_or_default();
+                let path = parts.nth(6).unwrap_or_default().to_string();
+                entries.push(file_status_from_xy(xy, path));
+            }
+            // "2 <XY> <sub> <mH> <mI> <mW> <hH> <hI> <X><score> <path><TAB><origPath>"
+            "2" => {
+                let mut parts = rest.splitn(9, ' ');
+                let xy = parts.next().unwrap_or_default();
+                let path_and_orig = parts.nth(7).unwrap_or_default();
+                let path = path_and_orig
+                    .split('\t')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                entries.push(file_status_from_xy(xy, path));
+            }
+            // "? <path>" (untracked)
+            "?" => entries.push(FileStatus {
+                path: rest.to_string(),
+                staged: false,
+                unstaged: true,
+                state: FileState::Untracked,
+            }),
+            // "u <XY> <sub> <m1> <m2> <m3> <mW> <h1> <h2> <h3> <path>" (unmerged)
+            "u" => {
+                let mut parts = rest.splitn(10, ' ');
+                let xy = parts.next().unwrap_or_default();
+                let path = parts.nth(8).unwrap_or_default().to_string();
+                entries.push(file_status_from_xy(xy, path));
+            }
+            // "! <path>" (ignored) isn't part of the changeset.
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
 pub fn git_stage_and_commit(
+    git: &Git,
     app: &mut App,
+    config: &RepoConfig,
+    paths: &[String],
     commit_title: &str,
     commit_details: &Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new("git").args(["add", "."]).output()?;
+    let commit_title = config.apply_commit_convention(commit_title);
+    let commit_title = commit_title.as_str();
+    let output = if paths.is_empty() {
+        git.command(&["add", "."]).output()?
+    } else {
+        let mut args = vec!["add"];
+        args.extend(paths.iter().map(String::as_str));
+        git.command(&args).output()?
+    };
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
         return Err("Failed to stage changes".into());
     }
-    app.add_log("INFO", "Staged all changes");
+    if paths.is_empty() {
+        app.add_log("INFO", "Staged all changes");
+    } else {
+        app.add_log("INFO", format!("Staged {} selected file(s)", paths.len()));
+    }
 
     let mut commit_message = commit_title.trim().to_string();
     if let Some(details) = commit_details {
         commit_message.push_str(&format!("\n\n{}", details.trim()));
     }
 
-    let output = Command::new("git")
-        .args(["commit", "-m", &commit_message])
-        .output()?;
+    let output = git.command(&["commit", "-m", &commit_message]).output()?;
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
         return Err("Failed to commit changes".into());
@@ -212,10 +476,12 @@ pub fn git_stage_and_commit(
     Ok(())
 }
 
-pub fn git_push_branch(app: &mut App, branch_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new("git")
-        .args(["push", "origin", branch_name])
-        .output()?;
+pub fn git_push_branch(
+    git: &Git,
+    app: &mut App,
+    branch_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = git.command(&["push", "origin", branch_name]).output()?;
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
         return Err("Failed to push branch".into());
@@ -226,12 +492,20 @@ pub fn git_push_branch(app: &mut App, branch_name: &str) -> Result<(), Box<dyn s
 
 pub fn create_pull_request(
     app: &mut App,
+    config: &RepoConfig,
     title: &str,
     body: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let output = Command::new("gh")
-        .args(["pr", "create", "--title", title, "--body", body])
-        .output()?;
+    let body = config.render_pr_body(title, body);
+    let mut command = Command::new("gh");
+    command.args(["pr", "create", "--title", title, "--body", &body]);
+    for label in &config.labels {
+        command.args(["--label", label]);
+    }
+    for reviewer in &config.reviewers {
+        command.args(["--reviewer", reviewer]);
+    }
+    let output = command.output()?;
     if !output.status.success() {
         app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
         return Err("Failed to create pull request".into());
@@ -0,0 +1,429 @@
+use crate::config::RepoConfig;
+use crate::git::Git;
+use crate::tui::App;
+use std::cell::RefCell;
+use std::process::Command;
+
+/// Abstraction over the git/gh operations gh-autopr needs, so the TUI can be
+/// driven against a real checkout, a scripted mock, or a throwaway test repo
+/// without changing a single call site.
+pub trait GitRepository {
+    fn diff_uncommitted(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>>;
+
+    fn diff_between_branches(
+        &self,
+        app: &mut App,
+        main_branch: &str,
+        current_branch: &str,
+    ) -> Result<String, Box<dyn std::error::Error>>;
+
+    fn main_branch(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>>;
+
+    fn current_branch(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>>;
+
+    fn stage_and_commit(
+        &self,
+        app: &mut App,
+        paths: &[String],
+        commit_title: &str,
+        commit_details: &Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn push_branch(
+        &self,
+        app: &mut App,
+        branch_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>>;
+
+    fn create_pull_request(
+        &self,
+        app: &mut App,
+        title: &str,
+        body: &str,
+    ) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Runs the real `git`/`gh` binaries via `Command`, exactly as gh-autopr did
+/// before this abstraction existed. Holds the `Git` wrapper every invocation
+/// is built through, and the repo's `.gh-autopr.toml` config. The wrapper
+/// runs `git` in the process's current directory; there is no `--repo` flag
+/// to point it at another checkout.
+pub struct RealRepository {
+    git: Git,
+    config: RepoConfig,
+}
+
+impl RealRepository {
+    pub fn new(git: Git, config: RepoConfig) -> Self {
+        Self { git, config }
+    }
+}
+
+impl GitRepository for RealRepository {
+    fn diff_uncommitted(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        crate::git_ops::git_diff_uncommitted(&self.git, app)
+    }
+
+    fn diff_between_branches(
+        &self,
+        app: &mut App,
+        main_branch: &str,
+        current_branch: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        crate::git_ops::git_diff_between_branches(
+            &self.git,
+            app,
+            &main_branch.to_string(),
+            &current_branch.to_string(),
+        )
+    }
+
+    fn main_branch(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        crate::git_ops::git_main_branch(&self.git, app, &self.config)
+    }
+
+    fn current_branch(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        crate::git_ops::git_current_branch(&self.git, app)
+    }
+
+    fn stage_and_commit(
+        &self,
+        app: &mut App,
+        paths: &[String],
+        commit_title: &str,
+        commit_details: &Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::git_ops::git_stage_and_commit(
+            &self.git,
+            app,
+            &self.config,
+            paths,
+            commit_title,
+            commit_details,
+        )
+    }
+
+    fn push_branch(
+        &self,
+        app: &mut App,
+        branch_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::git_ops::git_push_branch(&self.git, app, branch_name)
+    }
+
+    fn create_pull_request(
+        &self,
+        app: &mut App,
+        title: &str,
+        body: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        crate::git_ops::create_pull_request(app, &self.config, title, body)
+    }
+}
+
+/// A single recorded call against a [`MockRepository`], kept around so tests
+/// can assert on exactly what the TUI asked the git layer to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedCall {
+    DiffUncommitted,
+    DiffBetweenBranches { main_branch: String, current_branch: String },
+    MainBranch,
+    CurrentBranch,
+    StageAndCommit {
+        paths: Vec<String>,
+        commit_title: String,
+        commit_details: Option<String>,
+    },
+    PushBranch { branch_name: String },
+    CreatePullRequest { title: String, body: String },
+}
+
+/// Records every call it receives and replays canned responses, so the
+/// commit/PR pipeline can be exercised without touching a real repo.
+#[derive(Default)]
+pub struct MockRepository {
+    pub calls: RefCell<Vec<RecordedCall>>,
+    pub diff_uncommitted: String,
+    pub diff_between_branches: String,
+    pub main_branch: String,
+    pub current_branch: String,
+}
+
+impl GitRepository for MockRepository {
+    fn diff_uncommitted(&self, _app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::DiffUncommitted);
+        Ok(self.diff_uncommitted.clone())
+    }
+
+    fn diff_between_branches(
+        &self,
+        _app: &mut App,
+        main_branch: &str,
+        current_branch: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::DiffBetweenBranches {
+            main_branch: main_branch.to_string(),
+            current_branch: current_branch.to_string(),
+        });
+        Ok(self.diff_between_branches.clone())
+    }
+
+    fn main_branch(&self, _app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::MainBranch);
+        Ok(self.main_branch.clone())
+    }
+
+    fn current_branch(&self, _app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::CurrentBranch);
+        Ok(self.current_branch.clone())
+    }
+
+    fn stage_and_commit(
+        &self,
+        _app: &mut App,
+        paths: &[String],
+        commit_title: &str,
+        commit_details: &Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::StageAndCommit {
+            paths: paths.to_vec(),
+            commit_title: commit_title.to_string(),
+            commit_details: commit_details.clone(),
+        });
+        Ok(())
+    }
+
+    fn push_branch(
+        &self,
+        _app: &mut App,
+        branch_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::PushBranch {
+            branch_name: branch_name.to_string(),
+        });
+        Ok(())
+    }
+
+    fn create_pull_request(
+        &self,
+        _app: &mut App,
+        title: &str,
+        body: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.calls.borrow_mut().push(RecordedCall::CreatePullRequest {
+            title: title.to_string(),
+            body: body.to_string(),
+        });
+        Ok(())
+    }
+}
+
+/// Runs real `git` commands, but scoped to a throwaway repository created in
+/// a temp directory so integration-style tests can commit/branch for real
+/// without touching the developer's actual checkout.
+pub struct TestRepository {
+    root: tempfile::TempDir,
+}
+
+impl TestRepository {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let root = tempfile::tempdir()?;
+        let status = Command::new("git")
+            .args(["init"])
+            .current_dir(root.path())
+            .status()?;
+        if !status.success() {
+            return Err("Failed to initialize test repository".into());
+        }
+        let repo = Self { root };
+
+        // Set a local identity so `stage_and_commit` doesn't depend on the
+        // ambient global git config being present (e.g. on a fresh CI box).
+        repo.command(&["config", "user.name", "gh-autopr test"])
+            .status()?;
+        repo.command(&["config", "user.email", "gh-autopr-test@example.com"])
+            .status()?;
+
+        Ok(repo)
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        self.root.path()
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new("git");
+        command.current_dir(self.root.path()).args(args);
+        command
+    }
+}
+
+impl GitRepository for TestRepository {
+    fn diff_uncommitted(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        let output = self.command(&["diff", "--cached"]).output()?;
+        if !output.status.success() {
+            app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+            return Err("Failed to get diff".into());
+        }
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    }
+
+    fn diff_between_branches(
+        &self,
+        app: &mut App,
+        main_branch: &str,
+        current_branch: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let output = self
+            .command(&["diff", &format!("{}...{}", main_branch, current_branch)])
+            .output()?;
+        if !output.status.success() {
+            app.add_error(String::from_utf8_lossy(&output.stderr).to_string());
+            return Err("Failed to get diff between branches".into());
+        }
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    }
+
+    fn main_branch(&self, _app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        let output = self
+            .command(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .output()?;
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    }
+
+    fn current_branch(&self, app: &mut App) -> Result<String, Box<dyn std::error::Error>> {
+        self.main_branch(app)
+    }
+
+    fn stage_and_commit(
+        &self,
+        app: &mut App,
+        paths: &[String],
+        commit_title: &str,
+        commit_details: &Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let status = if paths.is_empty() {
+            self.command(&["add", "."]).status()?
+        } else {
+            let mut args = vec!["add"];
+            args.extend(paths.iter().map(String::as_str));
+            self.command(&args).status()?
+        };
+        if !status.success() {
+            app.add_error("Failed to stage changes in test repository".to_string());
+            return Err("Failed to stage changes".into());
+        }
+
+        let mut commit_message = commit_title.trim().to_string();
+        if let Some(details) = commit_details {
+            commit_message.push_str(&format!("\n\n{}", details.trim()));
+        }
+
+        let status = self
+            .command(&["commit", "-m", &commit_message])
+            .status()?;
+        if !status.success() {
+            app.add_error("Failed to commit changes in test repository".to_string());
+            return Err("Failed to commit changes".into());
+        }
+        Ok(())
+    }
+
+    fn push_branch(
+        &self,
+        _app: &mut App,
+        branch_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Err(format!(
+            "TestRepository has no remote to push branch {} to",
+            branch_name
+        )
+        .into())
+    }
+
+    fn create_pull_request(
+        &self,
+        _app: &mut App,
+        _title: &str,
+        _body: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Err("TestRepository cannot create pull requests, it has no remote".into())
+    }
+}
+
+/// Stages, commits, and opens a PR through whichever `GitRepository` the
+/// caller passes in. The TUI's commit/PR pipeline should call this instead
+/// of `git_ops::git_stage_and_commit`/`create_pull_request` directly once
+/// it's updated to hold a `&dyn GitRepository`; until then, this is the
+/// seam the mock/test backends exercise.
+pub fn commit_and_open_pr(
+    repo: &dyn GitRepository,
+    app: &mut App,
+    paths: &[String],
+    commit_title: &str,
+    commit_details: &Option<String>,
+    pr_title: &str,
+    pr_body: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    repo.stage_and_commit(app, paths, commit_title, commit_details)?;
+    repo.create_pull_request(app, pr_title, pr_body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::App;
+
+    #[test]
+    fn commit_and_open_pr_records_commit_message_and_pr_body() {
+        let mut app = App::new();
+        let repo = MockRepository::default();
+        let paths = vec!["src/lib.rs".to_string()];
+        let details = Some("Extra context for reviewers.".to_string());
+
+        commit_and_open_pr(
+            &repo,
+            &mut app,
+            &paths,
+            "Add widget support",
+            &details,
+            "Add widget support",
+            "This PR adds widget support.",
+        )
+        .unwrap();
+
+        let calls = repo.calls.borrow();
+        assert_eq!(
+            calls[0],
+            RecordedCall::StageAndCommit {
+                paths: paths.clone(),
+                commit_title: "Add widget support".to_string(),
+                commit_details: details,
+            }
+        );
+        assert_eq!(
+            calls[1],
+            RecordedCall::CreatePullRequest {
+                title: "Add widget support".to_string(),
+                body: "This PR adds widget support.".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_repository_commits_for_real() {
+        let mut app = App::new();
+        let repo = TestRepository::new().unwrap();
+        std::fs::write(repo.path().join("README.md"), "hello").unwrap();
+
+        repo.stage_and_commit(&mut app, &[], "Initial commit", &None)
+            .unwrap();
+
+        let log = Command::new("git")
+            .args(["log", "--oneline"])
+            .current_dir(repo.path())
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&log.stdout).contains("Initial commit"));
+    }
+}